@@ -1,7 +1,7 @@
 use failure::Fail;
 use parity_wasm::elements;
 
-use engine_shared::TypeMismatch;
+use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
 use types::{
     account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
     bytesrepr, system_contract_errors, AccessRights, ApiError, CLType, CLValueError,
@@ -89,6 +89,17 @@ pub enum Error {
         expected, actual
     )]
     InvalidKeyLength { expected: usize, actual: usize },
+    #[fail(display = "Root not found: {}", _0)]
+    RootNotFound(Blake2bHash),
+    #[fail(
+        display = "Insufficient authorization. Required {} authorizing accounts but found {}",
+        required, actual
+    )]
+    InsufficientAuthorization { required: usize, actual: usize },
+    #[fail(display = "Insufficient funds")]
+    InsufficientFunds,
+    #[fail(display = "Context is read-only")]
+    ReadOnlyContext,
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
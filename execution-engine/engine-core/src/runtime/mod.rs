@@ -1388,16 +1388,7 @@ where
     /// Intuition about the return value sense is to answer the question 'are we
     /// allowed to continue?'
     fn charge_gas(&mut self, amount: Gas) -> bool {
-        let prev = self.context.gas_counter();
-        match prev.checked_add(amount) {
-            // gas charge overflow protection
-            None => false,
-            Some(val) if val > self.context.gas_limit() => false,
-            Some(val) => {
-                self.context.set_gas_counter(val);
-                true
-            }
-        }
+        self.context.charge_gas(amount).is_ok()
     }
 
     fn gas(&mut self, amount: Gas) -> Result<(), Trap> {
@@ -1677,6 +1668,13 @@ where
         const METHOD_BALANCE: &str = "balance";
         const METHOD_TRANSFER: &str = "transfer";
 
+        // The mint unwraps its storage calls, so writing entry points are rejected up front
+        if self.context.is_read_only()
+            && [METHOD_MINT, METHOD_CREATE, METHOD_TRANSFER].contains(&entry_point_name)
+        {
+            return Err(Error::ReadOnlyContext);
+        }
+
         let state = self.context.state();
         let access_rights = {
             let mut keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -1718,6 +1716,7 @@ where
             phase,
             protocol_data,
         );
+        mint_context.set_read_only(self.context.is_read_only());
 
         let ret: CLValue = match entry_point_name {
             // Type: `fn mint(amount: U512) -> Result<URef, Error>`
@@ -1771,6 +1770,19 @@ where
         const ARG_AMOUNT: &str = "amount";
         const ARG_PURSE: &str = "purse";
 
+        // Proof of stake unwraps its storage calls, so writing entry points are rejected up front
+        if self.context.is_read_only()
+            && [
+                METHOD_BOND,
+                METHOD_UNBOND,
+                METHOD_SET_REFUND_PURSE,
+                METHOD_FINALIZE_PAYMENT,
+            ]
+            .contains(&entry_point_name)
+        {
+            return Err(Error::ReadOnlyContext);
+        }
+
         let state = self.context.state();
         let access_rights = {
             let mut keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -1792,7 +1804,7 @@ where
         let phase = self.context.phase();
         let protocol_data = self.context.protocol_data();
 
-        let runtime_context = RuntimeContext::new(
+        let mut runtime_context = RuntimeContext::new(
             state,
             EntryPointType::Contract,
             named_keys,
@@ -1812,6 +1824,7 @@ where
             phase,
             protocol_data,
         );
+        runtime_context.set_read_only(self.context.is_read_only());
 
         let mut runtime = Runtime::new(
             self.config,
@@ -2128,7 +2141,7 @@ where
 
         let host_buffer = None;
 
-        let context = RuntimeContext::new(
+        let mut context = RuntimeContext::new(
             self.context.state(),
             entry_point.entry_point_type(),
             &mut named_keys,
@@ -2148,6 +2161,7 @@ where
             self.context.phase(),
             self.context.protocol_data(),
         );
+        context.set_read_only(self.context.is_read_only());
 
         let mut runtime = Runtime {
             system_contract_cache,
@@ -2347,6 +2361,7 @@ where
     }
 
     fn create_contract_package_at_hash(&mut self) -> Result<([u8; 32], [u8; 32]), Error> {
+        self.context.validate_not_read_only()?;
         let addr = self.context.new_hash_address()?;
         let key = Key::Hash(addr);
         let (stored_value, access_key) = self.create_contract_value()?;
@@ -2363,6 +2378,7 @@ where
         mut existing_urefs: BTreeSet<URef>,
         output_size_ptr: u32,
     ) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_not_read_only()?;
         let contract_package_key = contract_package_hash.into();
 
         let mut contract_package: ContractPackage = self
@@ -2440,6 +2456,7 @@ where
         bytes_written_ptr: u32,
         version_ptr: u32,
     ) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_not_read_only()?;
         let contract_package_key = contract_package_hash.into();
         self.context.validate_key(&contract_package_key)?;
 
@@ -2533,6 +2550,7 @@ where
         contract_package_hash: ContractPackageHash,
         contract_hash: ContractHash,
     ) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_not_read_only()?;
         let contract_package_key = contract_package_hash.into();
         self.context.validate_key(&contract_package_key)?;
 
@@ -3201,6 +3219,7 @@ where
         package_key: ContractPackageHash,
         label: Group,
     ) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_not_read_only()?;
         let mut package: ContractPackage =
             self.context.get_validated_contract_package(package_key)?;
 
@@ -3254,6 +3273,7 @@ where
         label_size: u32,
         output_size_ptr: u32,
     ) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_not_read_only()?;
         let contract_package_hash = self.t_from_mem(package_ptr, package_size)?;
         let label: String = self.t_from_mem(label_ptr, label_size)?;
         let mut contract_package = self
@@ -3322,6 +3342,7 @@ where
         urefs_ptr: u32,
         urefs_size: u32,
     ) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_not_read_only()?;
         let contract_package_hash: ContractPackageHash =
             self.t_from_mem(package_ptr, package_size)?;
         let label: String = self.t_from_mem(label_ptr, label_size)?;
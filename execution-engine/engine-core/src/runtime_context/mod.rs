@@ -3,6 +3,7 @@ use std::{
     collections::{BTreeSet, HashMap, HashSet},
     convert::{TryFrom, TryInto},
     fmt::Debug,
+    ops::Bound,
     rc::Rc,
 };
 
@@ -12,25 +13,32 @@ use blake2::{
 };
 
 use engine_shared::{
-    account::Account, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
+    account::Account,
+    gas::Gas,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    TypeMismatch,
+};
+use engine_storage::{
+    global_state::{StateProvider, StateReader},
+    protocol_data::ProtocolData,
 };
-use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
     account::{
         AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
         UpdateKeyFailure, Weight,
     },
-    bytesrepr,
+    bytesrepr::{self, FromBytes, ToBytes},
     contracts::NamedKeys,
-    AccessRights, BlockTime, CLType, CLValue, Contract, ContractPackage, ContractPackageHash,
-    EntryPointAccess, EntryPointType, Key, Phase, ProtocolVersion, RuntimeArgs, URef,
-    KEY_HASH_LENGTH,
+    AccessRights, BlockTime, CLType, CLTyped, CLValue, CLValueError, Contract, ContractHash,
+    ContractPackage, ContractPackageHash, EntryPointAccess, EntryPointType, Key, Phase,
+    ProtocolVersion, RuntimeArgs, URef, KEY_HASH_LENGTH, U512,
 };
 
 use crate::{
     engine_state::execution_effect::ExecutionEffect,
     execution::{AddressGenerator, Error},
-    tracking_copy::{AddResult, TrackingCopy},
+    tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
     Address,
 };
 
@@ -84,6 +92,10 @@ pub fn validate_entry_point_access_with(
 /// Holds information specific to the deployed contract.
 pub struct RuntimeContext<'a, R> {
     tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    // Values read via `read_gs` during this execution, keyed by normalized key. Invalidated on
+    // writes, and cleared whenever the tracking copy is handed out as it may be written to
+    // elsewhere.
+    read_cache: RefCell<HashMap<Key, StoredValue>>,
     // Enables look up of specific uref based on human-readable name
     named_keys: &'a mut NamedKeys,
     // Used to check uref is known before use (prevents forging urefs)
@@ -106,6 +118,8 @@ pub struct RuntimeContext<'a, R> {
     phase: Phase,
     protocol_data: ProtocolData,
     entry_point_type: EntryPointType,
+    // When set, every method which would stage a change to global state fails instead
+    read_only: bool,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -136,6 +150,7 @@ where
     ) -> Self {
         RuntimeContext {
             tracking_copy,
+            read_cache: RefCell::new(HashMap::new()),
             entry_point_type,
             named_keys,
             access_rights,
@@ -153,6 +168,7 @@ where
             correlation_id,
             phase,
             protocol_data,
+            read_only: false,
         }
     }
 
@@ -160,6 +176,49 @@ where
         &self.authorization_keys
     }
 
+    /// Checks whether `account` is one of the authorization keys of the deploy.
+    pub fn is_authorized_by(&self, account: AccountHash) -> bool {
+        self.authorization_keys.contains(&account)
+    }
+
+    /// Returns the combined weight of the deploy's authorization keys that are associated keys of
+    /// the account.
+    pub fn authorization_weight(&self) -> Weight {
+        self.account()
+            .authorization_weight(&self.authorization_keys)
+    }
+
+    /// Checks whether the associated-key and action-threshold methods would be permitted, so that
+    /// callers can check up front rather than handling `PermissionDenied`.
+    pub fn can_manage_keys(&self) -> bool {
+        self.is_valid_context()
+            && self
+                .account()
+                .can_manage_keys_with(&self.authorization_keys)
+    }
+
+    /// Checks that at least `threshold` of the distinct `accounts` are among the authorization keys
+    /// of the deploy.
+    pub fn require_authorization(
+        &self,
+        accounts: &[AccountHash],
+        threshold: usize,
+    ) -> Result<(), Error> {
+        let actual = accounts
+            .iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|account| self.is_authorized_by(**account))
+            .count();
+        if actual < threshold {
+            return Err(Error::InsufficientAuthorization {
+                required: threshold,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
     pub fn named_keys_get(&self, name: &str) -> Option<&Key> {
         self.named_keys.get(name)
     }
@@ -168,6 +227,20 @@ where
         &self.named_keys
     }
 
+    /// Returns up to `limit` named keys in sorted order, starting after the one named
+    /// `start_after`, or from the beginning if it is `None`.
+    pub fn named_keys_page(&self, start_after: Option<&str>, limit: usize) -> Vec<(&str, &Key)> {
+        let lower_bound = match start_after {
+            Some(name) => Bound::Excluded(name),
+            None => Bound::Unbounded,
+        };
+        self.named_keys
+            .range::<str, _>((lower_bound, Bound::Unbounded))
+            .take(limit)
+            .map(|(name, key)| (name.as_str(), key))
+            .collect()
+    }
+
     pub fn named_keys_mut(&mut self) -> &mut NamedKeys {
         &mut self.named_keys
     }
@@ -176,18 +249,22 @@ where
         self.named_keys.contains_key(name)
     }
 
-    // Helper function to avoid duplication in `remove_uref`.
-    fn remove_key_from_contract(
+    // Helper function to avoid duplication in `remove_keys`.
+    fn remove_keys_from_contract(
         &mut self,
         key: Key,
         mut contract: Contract,
-        name: &str,
+        names: &[&str],
     ) -> Result<(), Error> {
-        if contract.remove_named_key(name).is_none() {
+        let mut removed = false;
+        for name in names {
+            removed |= contract.remove_named_key(name).is_some();
+        }
+        if !removed {
             return Ok(());
         }
         let contract_value = StoredValue::Contract(contract);
-        self.tracking_copy.borrow_mut().write(key, contract_value);
+        self.write_unsafe(key, contract_value);
         Ok(())
     }
 
@@ -196,18 +273,29 @@ where
     /// also persistable map (one that is found in the
     /// TrackingCopy/GlobalState).
     pub fn remove_key(&mut self, name: &str) -> Result<(), Error> {
+        self.remove_keys(&[name])
+    }
+
+    /// Removes all of the given Keys from the `named_keys` map of the current context, in the same
+    /// manner as `remove_key`, but with a single write to the TrackingCopy/GlobalState.
+    ///
+    /// Names which are not present are skipped.
+    pub fn remove_keys(&mut self, names: &[&str]) -> Result<(), Error> {
+        self.validate_not_read_only()?;
         match self.base_key() {
             account_hash @ Key::Account(_) => {
                 let account: Account = {
                     let mut account: Account = self.read_gs_typed(&account_hash)?;
-                    account.named_keys_mut().remove(name);
+                    for name in names {
+                        account.named_keys_mut().remove(*name);
+                    }
                     account
                 };
-                self.named_keys.remove(name);
+                for name in names {
+                    self.named_keys.remove(*name);
+                }
                 let account_value = self.account_to_validated_value(account)?;
-                self.tracking_copy
-                    .borrow_mut()
-                    .write(account_hash, account_value);
+                self.write_unsafe(account_hash, account_value);
                 Ok(())
             }
             contract_uref @ Key::URef(_) => {
@@ -222,13 +310,17 @@ where
                     value.try_into().map_err(Error::TypeMismatch)?
                 };
 
-                self.named_keys.remove(name);
-                self.remove_key_from_contract(contract_uref, contract, name)
+                for name in names {
+                    self.named_keys.remove(*name);
+                }
+                self.remove_keys_from_contract(contract_uref, contract, names)
             }
             contract_hash @ Key::Hash(_) => {
                 let contract: Contract = self.read_gs_typed(&contract_hash)?;
-                self.named_keys.remove(name);
-                self.remove_key_from_contract(contract_hash, contract, name)
+                for name in names {
+                    self.named_keys.remove(*name);
+                }
+                self.remove_keys_from_contract(contract_hash, contract, names)
             }
         }
     }
@@ -265,7 +357,12 @@ where
         Rc::clone(&self.hash_address_generator)
     }
 
+    /// Returns the tracking copy backing this context.
+    ///
+    /// As the caller may write to it directly, or hand it to a child context which does, this also
+    /// clears the read cache so that later reads through this context see those writes.
     pub fn state(&self) -> Rc<RefCell<TrackingCopy<R>>> {
+        self.read_cache.borrow_mut().clear();
         Rc::clone(&self.tracking_copy)
     }
 
@@ -281,6 +378,66 @@ where
         self.gas_counter = new_gas_counter;
     }
 
+    /// Returns the gas left before the gas limit is reached, or zero if it has been exceeded.
+    pub fn gas_remaining(&self) -> Gas {
+        Gas::new(
+            self.gas_limit
+                .value()
+                .saturating_sub(self.gas_counter.value()),
+        )
+    }
+
+    /// Checks whether `amount` of gas can be charged without exceeding the gas limit.
+    pub fn has_gas_for(&self, amount: Gas) -> bool {
+        amount <= self.gas_remaining()
+    }
+
+    /// Charges `amount` of gas, failing with `Error::GasLimit` without updating the gas counter if
+    /// the gas limit would be exceeded.
+    pub fn charge_gas(&mut self, amount: Gas) -> Result<(), Error> {
+        match self.gas_counter.checked_add(amount) {
+            // gas charge overflow protection
+            None => Err(Error::GasLimit),
+            Some(val) if val > self.gas_limit => Err(Error::GasLimit),
+            Some(val) => {
+                self.gas_counter = val;
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs `f` with the gas limit lowered so that it can consume at most `sub_limit` gas, then
+    /// restores the original gas limit.
+    ///
+    /// Returns the result of `f` along with the amount of gas it consumed.
+    pub fn run_with_gas_limit<T>(
+        &mut self,
+        sub_limit: Gas,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> (T, Gas) {
+        let gas_limit = self.gas_limit;
+        let gas_counter = self.gas_counter;
+        self.gas_limit = match gas_counter.checked_add(sub_limit) {
+            Some(sub_gas_limit) if sub_gas_limit < gas_limit => sub_gas_limit,
+            _ => gas_limit,
+        };
+        let result = f(self);
+        self.gas_limit = gas_limit;
+        // `f` may have lowered the gas counter via `set_gas_counter`
+        let consumed = Gas::new(self.gas_counter.value().saturating_sub(gas_counter.value()));
+        (result, consumed)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Puts the context into (or takes it out of) read-only mode, in which all methods which would
+    /// modify global state or the named keys fail with `Error::ReadOnlyContext`.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
     pub fn base_key(&self) -> Key {
         self.base_key
     }
@@ -309,13 +466,27 @@ where
     }
 
     pub fn new_uref(&mut self, value: StoredValue) -> Result<URef, Error> {
+        self.new_uref_with_access_rights(value, AccessRights::READ_ADD_WRITE)
+    }
+
+    /// Creates a new URef with the given access rights and writes `value` under it.
+    ///
+    /// The value is written regardless of `rights`, so e.g. a read-only reference to some data can
+    /// be handed out.
+    pub fn new_uref_with_access_rights(
+        &mut self,
+        value: StoredValue,
+        rights: AccessRights,
+    ) -> Result<URef, Error> {
+        self.validate_not_read_only()?;
         let uref = {
             let addr = self.uref_address_generator.borrow_mut().create_address();
-            URef::new(addr, AccessRights::READ_ADD_WRITE)
+            URef::new(addr, rights)
         };
         let key = Key::URef(uref);
         self.insert_uref(uref);
-        self.write_gs(key, value)?;
+        self.validate_value(&value)?;
+        self.write_unsafe(key, value);
         Ok(uref)
     }
 
@@ -327,6 +498,8 @@ where
 
     /// Puts `key` to the map of named keys of current context.
     pub fn put_key(&mut self, name: String, key: Key) -> Result<(), Error> {
+        self.validate_not_read_only()?;
+
         // No need to perform actual validation on the base key because an account or contract (i.e.
         // the element stored under `base_key`) is allowed to add new named keys to itself.
         let named_key_value = StoredValue::CLValue(CLValue::from_t((name.clone(), key))?);
@@ -360,6 +533,7 @@ where
     }
 
     pub fn write_ls(&mut self, key_bytes: &[u8], cl_value: CLValue) -> Result<(), Error> {
+        self.validate_not_read_only()?;
         let actual_length = key_bytes.len();
         if actual_length != KEY_HASH_LENGTH {
             return Err(Error::InvalidKeyLength {
@@ -368,22 +542,91 @@ where
             });
         }
         let hash: [u8; KEY_HASH_LENGTH] = key_bytes.try_into().unwrap();
-        self.tracking_copy
-            .borrow_mut()
-            .write(hash.into(), StoredValue::CLValue(cl_value));
+        self.write_unsafe(hash.into(), StoredValue::CLValue(cl_value));
         Ok(())
     }
 
+    /// Reads the value stored in local storage under `key_bytes` as a `T`, failing with
+    /// `Error::TypeMismatch` if a value of a different type is stored there.
+    pub fn read_ls_typed<T>(&mut self, key_bytes: &[u8]) -> Result<Option<T>, Error>
+    where
+        T: FromBytes + CLTyped,
+    {
+        let cl_value = match self.read_ls(key_bytes)? {
+            Some(cl_value) => cl_value,
+            None => return Ok(None),
+        };
+        cl_value.into_t().map(Some).map_err(|error| match error {
+            CLValueError::Type(type_mismatch) => {
+                let expected = format!("{:?}", type_mismatch.expected);
+                let found = format!("{:?}", type_mismatch.found);
+                Error::TypeMismatch(TypeMismatch::new(expected, found))
+            }
+            error => error.into(),
+        })
+    }
+
+    /// Writes `value` to local storage under `key_bytes`.
+    pub fn write_ls_typed<T>(&mut self, key_bytes: &[u8], value: T) -> Result<(), Error>
+    where
+        T: ToBytes + CLTyped,
+    {
+        let cl_value = CLValue::from_t(value)?;
+        self.write_ls(key_bytes, cl_value)
+    }
+
     pub fn read_gs(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         self.validate_readable(key)?;
         self.validate_key(key)?;
+        self.read_gs_cached(key)
+    }
 
-        self.tracking_copy
+    /// Reads the values stored under each of `keys`, returned in the same order.
+    ///
+    /// All keys are validated before any of them is read, so the call fails as a whole if any key
+    /// isn't readable.
+    pub fn read_gs_many(&mut self, keys: &[Key]) -> Result<Vec<Option<StoredValue>>, Error> {
+        for key in keys {
+            self.validate_readable(key)?;
+            self.validate_key(key)?;
+        }
+        keys.iter().map(|key| self.read_gs_cached(key)).collect()
+    }
+
+    // Reads through the read cache without validating `key`.
+    fn read_gs_cached(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
+        let normalized_key = key.normalize();
+        if let Some(value) = self.read_cache.borrow().get(&normalized_key) {
+            return Ok(Some(value.clone()));
+        }
+
+        let maybe_value = self
+            .tracking_copy
             .borrow_mut()
             .read(self.correlation_id, key)
-            .map_err(Into::into)
+            .map_err(Into::into)?;
+        if let Some(value) = &maybe_value {
+            self.read_cache
+                .borrow_mut()
+                .insert(normalized_key, value.clone());
+        }
+        Ok(maybe_value)
+    }
+
+    /// Checks whether a value is stored under `key`, performing the same validation as `read_gs`
+    /// but without handing the value back.
+    ///
+    /// Global state offers no cheaper existence check than a full read, so the value read is kept
+    /// in the read cache for a following `read_gs` to reuse.
+    pub fn key_exists(&mut self, key: &Key) -> Result<bool, Error> {
+        self.validate_readable(key)?;
+        self.validate_key(key)?;
+        self.read_gs_cached(key)
+            .map(|maybe_value| maybe_value.is_some())
     }
 
+    /// Bypasses the read cache used by `read_gs`.
+    ///
     /// DO NOT EXPOSE THIS VIA THE FFI
     pub fn read_gs_direct(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         self.tracking_copy
@@ -392,6 +635,31 @@ where
             .map_err(Into::into)
     }
 
+    /// Reads the value stored under `key` as of the global state identified by `root`, rather
+    /// than through the current `TrackingCopy`.
+    ///
+    /// The context only holds a reader for the current state root, so callers must pass in the
+    /// global state `root` came from.
+    ///
+    /// No access rights validation is performed, so this is a read-only debugging API.
+    /// DO NOT EXPOSE THIS VIA THE FFI
+    pub fn read_at_root<S>(
+        &self,
+        state: &S,
+        root: Blake2bHash,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Error>
+    where
+        S: StateProvider,
+        S::Error: Into<Error>,
+    {
+        let reader = state
+            .checkout(root)
+            .map_err(Into::into)?
+            .ok_or_else(|| Error::RootNotFound(root))?;
+        reader.read(self.correlation_id, key).map_err(Into::into)
+    }
+
     /// This method is a wrapper over `read_gs` in the sense that it extracts the type held by a
     /// `StoredValue` stored in the global state in a type safe manner.
     ///
@@ -415,10 +683,11 @@ where
     }
 
     pub fn write_gs(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        self.validate_not_read_only()?;
         self.validate_writeable(&key)?;
         self.validate_key(&key)?;
         self.validate_value(&value)?;
-        self.tracking_copy.borrow_mut().write(key, value);
+        self.write_unsafe(key, value);
         Ok(())
     }
 
@@ -435,10 +704,11 @@ where
     }
 
     pub fn write_account(&mut self, key: Key, account: Account) -> Result<(), Error> {
+        self.validate_not_read_only()?;
         if let Key::Account(_) = key {
             self.validate_key(&key)?;
             let account_value = self.account_to_validated_value(account)?;
-            self.tracking_copy.borrow_mut().write(key, account_value);
+            self.write_unsafe(key, account_value);
             Ok(())
         } else {
             panic!("Do not use this function for writing non-account keys")
@@ -457,10 +727,11 @@ where
         &mut self,
         contract: StoredValue,
     ) -> Result<[u8; KEY_HASH_LENGTH], Error> {
+        self.validate_not_read_only()?;
         let new_hash = self.new_hash_address()?;
         self.validate_value(&contract)?;
         let hash_key = Key::Hash(new_hash);
-        self.tracking_copy.borrow_mut().write(hash_key, contract);
+        self.write_unsafe(hash_key, contract);
         Ok(new_hash)
     }
 
@@ -534,8 +805,14 @@ where
                 .named_keys()
                 .values()
                 .try_for_each(|key| self.validate_key(key)),
-            // TODO: anything to validate here?
-            StoredValue::ContractPackage(_) => Ok(()),
+            StoredValue::ContractPackage(contract_package) => {
+                self.validate_uref(&contract_package.access_key())?;
+                contract_package
+                    .groups()
+                    .values()
+                    .flatten()
+                    .try_for_each(|uref| self.validate_uref(uref))
+            }
         }
     }
 
@@ -583,6 +860,14 @@ where
         Ok(keys)
     }
 
+    pub fn validate_not_read_only(&self) -> Result<(), Error> {
+        if self.read_only {
+            Err(Error::ReadOnlyContext)
+        } else {
+            Ok(())
+        }
+    }
+
     fn validate_readable(&self, key: &Key) -> Result<(), Error> {
         if self.is_readable(&key) {
             Ok(())
@@ -644,13 +929,20 @@ where
     /// value stored under `key` has different type, then `TypeMismatch`
     /// errors is returned.
     pub fn add_gs(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        self.validate_not_read_only()?;
         self.validate_addable(&key)?;
         self.validate_key(&key)?;
         self.validate_value(&value)?;
         self.add_unsafe(key, value)
     }
 
+    fn write_unsafe(&mut self, key: Key, value: StoredValue) {
+        self.read_cache.borrow_mut().remove(&key.normalize());
+        self.tracking_copy.borrow_mut().write(key, value);
+    }
+
     fn add_unsafe(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        self.read_cache.borrow_mut().remove(&key.normalize());
         match self
             .tracking_copy
             .borrow_mut()
@@ -669,6 +961,8 @@ where
         account_hash: AccountHash,
         weight: Weight,
     ) -> Result<(), Error> {
+        self.validate_not_read_only()?;
+
         // Check permission to modify associated keys
         if !self.is_valid_context() {
             // Exit early with error to avoid mutations
@@ -699,12 +993,14 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.tracking_copy.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value);
 
         Ok(())
     }
 
     pub fn remove_associated_key(&mut self, account_hash: AccountHash) -> Result<(), Error> {
+        self.validate_not_read_only()?;
+
         // Check permission to modify associated keys
         if !self.is_valid_context() {
             // Exit early with error to avoid mutations
@@ -733,7 +1029,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.tracking_copy.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value);
 
         Ok(())
     }
@@ -743,6 +1039,8 @@ where
         account_hash: AccountHash,
         weight: Weight,
     ) -> Result<(), Error> {
+        self.validate_not_read_only()?;
+
         // Check permission to modify associated keys
         if !self.is_valid_context() {
             // Exit early with error to avoid mutations
@@ -771,7 +1069,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.tracking_copy.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value);
 
         Ok(())
     }
@@ -781,6 +1079,8 @@ where
         action_type: ActionType,
         threshold: Weight,
     ) -> Result<(), Error> {
+        self.validate_not_read_only()?;
+
         // Check permission to modify associated keys
         if !self.is_valid_context() {
             // Exit early with error to avoid mutations
@@ -809,7 +1109,48 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.tracking_copy.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value);
+
+        Ok(())
+    }
+
+    /// Applies all the mutations made by `f` to the account of the current context with a single
+    /// read and a single write.
+    ///
+    /// If `f` returns an error nothing is written, so the account is never left partially updated.
+    pub fn update_account<F>(&mut self, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Account) -> Result<(), Error>,
+    {
+        self.validate_not_read_only()?;
+
+        // Check permission to modify associated keys
+        if !self.is_valid_context() {
+            // Exit early with error to avoid mutations
+            return Err(UpdateKeyFailure::PermissionDenied.into());
+        }
+
+        if !self
+            .account()
+            .can_manage_keys_with(&self.authorization_keys)
+        {
+            // Exit early if authorization keys weight doesn't exceed required
+            // key management threshold
+            return Err(UpdateKeyFailure::PermissionDenied.into());
+        }
+
+        // Converts an account's public key into a URef
+        let key = Key::Account(self.account().account_hash());
+
+        // Take an account out of the global state
+        let mut account: Account = self.read_gs_typed(&key)?;
+
+        // Exit early in case of error without updating global state
+        f(&mut account)?;
+
+        let account_value = self.account_to_validated_value(account)?;
+
+        self.write_unsafe(key, account_value);
 
         Ok(())
     }
@@ -838,6 +1179,80 @@ where
         Ok(self.account().main_purse())
     }
 
+    // Reads the balance held under `balance_key`, or `None` if there is none. Unlike
+    // `TrackingCopyExt::get_purse_balance`, errors from the reader are passed up.
+    fn read_purse_balance(&mut self, balance_key: Key) -> Result<Option<U512>, Error> {
+        match self
+            .tracking_copy
+            .borrow_mut()
+            .read(self.correlation_id, &balance_key)
+            .map_err(Into::into)?
+        {
+            Some(stored_value) => {
+                let cl_value: CLValue = stored_value.try_into().map_err(Error::TypeMismatch)?;
+                Ok(Some(cl_value.into_t()?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Gets the balance of `purse`, or `None` if the mint doesn't know about it.
+    pub fn get_purse_balance(&mut self, purse: URef) -> Result<Option<U512>, Error> {
+        self.validate_uref(&purse)?;
+
+        let balance_key = match self
+            .tracking_copy
+            .borrow_mut()
+            .get_purse_balance_key(self.correlation_id, purse.into())
+        {
+            Ok(balance_key) => balance_key,
+            Err(Error::URefNotFound(_)) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        self.read_purse_balance(balance_key)
+    }
+
+    /// Moves `amount` from the `source` purse to the `target` purse, failing with
+    /// `Error::InsufficientFunds` if `source` doesn't hold enough.
+    ///
+    /// Named so as not to shadow `Mint::transfer`, which is also implemented for `RuntimeContext`.
+    pub fn transfer_from_purse_to_purse(
+        &mut self,
+        source: URef,
+        target: URef,
+        amount: U512,
+    ) -> Result<(), Error> {
+        self.validate_not_read_only()?;
+        let source_key = Key::URef(source);
+        let target_key = Key::URef(target);
+        self.validate_writeable(&source_key)?;
+        self.validate_key(&source_key)?;
+        self.validate_addable(&target_key)?;
+        self.validate_key(&target_key)?;
+
+        let (source_balance_key, target_balance_key) = {
+            let mut tracking_copy = self.tracking_copy.borrow_mut();
+            (
+                tracking_copy.get_purse_balance_key(self.correlation_id, source_key)?,
+                tracking_copy.get_purse_balance_key(self.correlation_id, target_key)?,
+            )
+        };
+        let source_balance = self
+            .read_purse_balance(source_balance_key)?
+            .ok_or_else(|| Error::KeyNotFound(source_balance_key))?;
+        if source_balance < amount {
+            return Err(Error::InsufficientFunds);
+        }
+        self.write_unsafe(
+            source_balance_key,
+            StoredValue::CLValue(CLValue::from_t(source_balance - amount)?),
+        );
+        self.add_unsafe(
+            target_balance_key,
+            StoredValue::CLValue(CLValue::from_t(amount)?),
+        )
+    }
+
     /// Gets entry point type.
     pub fn entry_point_type(&self) -> EntryPointType {
         self.entry_point_type
@@ -854,4 +1269,14 @@ where
         self.validate_uref(&contract_package.access_key())?;
         Ok(contract_package)
     }
+
+    /// Gets the contract package owning the contract stored under `contract_hash`, with its
+    /// access_key validated against current context.
+    pub fn package_for_contract(
+        &mut self,
+        contract_hash: ContractHash,
+    ) -> Result<ContractPackage, Error> {
+        let contract: Contract = self.read_gs_typed(&Key::from(contract_hash))?;
+        self.get_validated_contract_package(contract.contract_package_hash())
+    }
 }
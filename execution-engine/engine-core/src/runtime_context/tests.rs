@@ -5,10 +5,12 @@ use std::{
     rc::Rc,
 };
 
+use parity_wasm::elements::Module;
 use rand::RngCore;
+use wasmi::{memory_units::Pages, MemoryInstance};
 
 use engine_shared::{
-    account::{Account, AssociatedKeys},
+    account::{Account, ActionThresholds, AssociatedKeys},
     additive_map::AdditiveMap,
     gas::Gas,
     newtypes::CorrelationId,
@@ -23,19 +25,22 @@ use types::{
     account::{
         AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, Weight,
     },
-    contracts::NamedKeys,
-    AccessRights, BlockTime, CLValue, Contract, EntryPointType, EntryPoints, Key, Phase,
-    ProtocolVersion, RuntimeArgs, URef, KEY_HASH_LENGTH,
+    contracts::{Group, NamedKeys},
+    AccessRights, BlockTime, CLValue, Contract, ContractPackage, EntryPointType, EntryPoints, Key,
+    Phase, ProtocolVersion, RuntimeArgs, URef, KEY_HASH_LENGTH, U512,
 };
 
 use super::{Address, Error, RuntimeContext};
 use crate::{
-    execution::AddressGenerator, runtime::extract_access_rights_from_keys,
+    engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
+    execution::AddressGenerator,
+    runtime::{extract_access_rights_from_keys, Runtime},
     tracking_copy::TrackingCopy,
 };
 
 const DEPLOY_HASH: [u8; 32] = [1u8; 32];
 const PHASE: Phase = Phase::Session;
+const GAS_LIMIT: u64 = 500_000_000;
 
 fn mock_tracking_copy(
     init_key: Key,
@@ -129,7 +134,7 @@ fn mock_runtime_context<'a>(
         base_key,
         BlockTime::new(0),
         [1u8; 32],
-        Gas::default(),
+        Gas::new(U512::from(GAS_LIMIT)),
         Gas::default(),
         Rc::new(RefCell::new(hash_address_generator)),
         Rc::new(RefCell::new(uref_address_generator)),
@@ -831,3 +836,979 @@ fn validate_valid_purse_of_an_account() {
     let purse = URef::new([53; 32], AccessRights::READ_ADD_WRITE);
     assert!(runtime_context.validate_uref(&purse).is_err());
 }
+
+#[test]
+fn should_read_value_at_past_root() {
+    let correlation_id = CorrelationId::new();
+    let account_hash = AccountHash::new([0u8; 32]);
+    let (base_key, account) = mock_account(account_hash);
+    let mut rng = rand::thread_rng();
+    let key = random_hash(&mut rng);
+    let old_value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let new_value = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+
+    let hist = InMemoryGlobalState::empty().unwrap();
+    let commit = |prestate_hash, effects| match hist
+        .commit(correlation_id, prestate_hash, effects)
+        .expect("should commit")
+    {
+        CommitResult::Success { state_root, .. } => state_root,
+        other => panic!("Commiting changes to test History failed: {:?}.", other),
+    };
+
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        base_key,
+        Transform::Write(StoredValue::Account(account.clone())),
+    );
+    effects.insert(key, Transform::Write(old_value.clone()));
+    let old_root = commit(hist.empty_root_hash, effects);
+
+    let mut effects = AdditiveMap::new();
+    effects.insert(key, Transform::Write(new_value.clone()));
+    let new_root = commit(old_root, effects);
+
+    let reader = hist
+        .checkout(new_root)
+        .expect("Checkout should not throw errors.")
+        .expect("Root hash should exist.");
+    let mut named_keys = NamedKeys::new();
+    let mut runtime_context = RuntimeContext::new(
+        Rc::new(RefCell::new(TrackingCopy::new(reader))),
+        EntryPointType::Session,
+        &mut named_keys,
+        HashMap::new(),
+        RuntimeArgs::new(),
+        BTreeSet::from_iter(vec![account_hash]),
+        &account,
+        base_key,
+        BlockTime::new(0),
+        DEPLOY_HASH,
+        Gas::default(),
+        Gas::default(),
+        Rc::new(RefCell::new(AddressGenerator::new(&DEPLOY_HASH, PHASE))),
+        Rc::new(RefCell::new(AddressGenerator::new(&DEPLOY_HASH, PHASE))),
+        ProtocolVersion::V1_0_0,
+        correlation_id,
+        PHASE,
+        Default::default(),
+    );
+
+    assert_eq!(runtime_context.read_gs(&key).unwrap(), Some(new_value));
+    assert_eq!(
+        runtime_context.read_at_root(&hist, old_root, &key).unwrap(),
+        Some(old_value)
+    );
+}
+
+#[test]
+fn should_update_account_atomically() {
+    let access_rights = HashMap::new();
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let first_key = AccountHash::new([42; 32]);
+        let second_key = AccountHash::new([43; 32]);
+
+        runtime_context
+            .update_account(|account| {
+                account.add_associated_key(first_key, Weight::new(100))?;
+                account.add_associated_key(second_key, Weight::new(100))?;
+                account.set_action_threshold(ActionType::KeyManagement, Weight::new(200))?;
+                Ok(())
+            })
+            .expect("should update account");
+
+        let effect = runtime_context.effect();
+        let transform = effect.transforms.get(&runtime_context.base_key()).unwrap();
+        let account = match transform {
+            Transform::Write(StoredValue::Account(account)) => account,
+            _ => panic!("Invalid transform operation found"),
+        };
+        assert_eq!(
+            account.get_associated_key_weight(first_key),
+            Some(&Weight::new(100))
+        );
+        assert_eq!(
+            account.get_associated_key_weight(second_key),
+            Some(&Weight::new(100))
+        );
+        assert_eq!(
+            account.action_thresholds().key_management(),
+            &Weight::new(200)
+        );
+
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+#[test]
+fn should_not_update_account_partially() {
+    let access_rights = HashMap::new();
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let account_hash = AccountHash::new([42; 32]);
+
+        let err = runtime_context
+            .update_account(|account| {
+                account.add_associated_key(account_hash, Weight::new(100))?;
+                // Adding the same key again fails after the first mutation was applied
+                account.add_associated_key(account_hash, Weight::new(100))?;
+                Ok(())
+            })
+            .expect_err("should fail to update account");
+
+        match err {
+            Error::AddKeyFailure(AddKeyFailure::DuplicateKey) => {}
+            e => panic!("Invalid error variant: {:?}", e),
+        }
+
+        // The account in global state is left untouched
+        let base_key = runtime_context.base_key();
+        let account = runtime_context
+            .read_gs(&base_key)?
+            .expect("Account is found in GS.");
+        assert_eq!(
+            account,
+            StoredValue::Account(runtime_context.account().clone())
+        );
+
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+#[test]
+fn should_resolve_package_for_contract() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let access_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![access_key]);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let mut rng = rand::thread_rng();
+        let package_hash = random_hash(&mut rng).into_hash().expect("should be hash");
+        let contract_hash = random_hash(&mut rng).into_hash().expect("should be hash");
+
+        let mut contract_package = ContractPackage::new(
+            access_key.into_uref().expect("should be uref"),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        );
+        contract_package.insert_contract_version(1, contract_hash);
+        let contract = Contract::new(
+            package_hash,
+            [0u8; 32],
+            NamedKeys::new(),
+            EntryPoints::default(),
+            ProtocolVersion::V1_0_0,
+        );
+
+        let state = runtime_context.state();
+        state.borrow_mut().write(
+            Key::from(package_hash),
+            StoredValue::ContractPackage(contract_package.clone()),
+        );
+        state
+            .borrow_mut()
+            .write(Key::from(contract_hash), StoredValue::Contract(contract));
+
+        let result = runtime_context.package_for_contract(contract_hash)?;
+        assert_eq!(result, contract_package);
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+#[test]
+fn should_require_authorization_threshold() {
+    let authorized = AccountHash::new([0; 32]);
+    let unauthorized = AccountHash::new([42; 32]);
+    let query = |runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        assert!(runtime_context.is_authorized_by(authorized));
+        assert!(!runtime_context.is_authorized_by(unauthorized));
+
+        runtime_context.require_authorization(&[authorized, unauthorized], 1)?;
+
+        match runtime_context.require_authorization(&[authorized, unauthorized], 2) {
+            Err(Error::InsufficientAuthorization {
+                required: 2,
+                actual: 1,
+            }) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+
+        // Listing the same account twice doesn't count it twice
+        match runtime_context.require_authorization(&[authorized, authorized], 2) {
+            Err(Error::InsufficientAuthorization {
+                required: 2,
+                actual: 1,
+            }) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_run_with_lower_gas_limit() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.charge_gas(Gas::new(U512::from(100)))?;
+
+        let (result, consumed) =
+            runtime_context.run_with_gas_limit(Gas::new(U512::from(50)), |sub_context| {
+                sub_context.charge_gas(Gas::new(U512::from(40)))?;
+                sub_context.charge_gas(Gas::new(U512::from(40)))
+            });
+        match result {
+            Err(Error::GasLimit) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+        assert_eq!(consumed, Gas::new(U512::from(40)));
+        assert_eq!(runtime_context.gas_limit(), Gas::new(U512::from(GAS_LIMIT)));
+
+        // The outer context continues with its original limit
+        runtime_context.charge_gas(Gas::new(U512::from(1_000)))?;
+        assert_eq!(runtime_context.gas_counter(), Gas::new(U512::from(1_140)));
+
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_not_underflow_when_gas_counter_is_lowered_with_gas_limit() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.charge_gas(Gas::new(U512::from(100)))?;
+
+        let ((), consumed) = runtime_context
+            .run_with_gas_limit(Gas::new(U512::from(50)), |sub_context| {
+                sub_context.set_gas_counter(Gas::default())
+            });
+        assert_eq!(consumed, Gas::default());
+        assert_eq!(runtime_context.gas_counter(), Gas::default());
+
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_charge_gas_under_limit() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.charge_gas(Gas::new(U512::from(GAS_LIMIT - 1)))?;
+        assert_eq!(
+            runtime_context.gas_counter(),
+            Gas::new(U512::from(GAS_LIMIT - 1))
+        );
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_charge_gas_exactly_at_limit() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.charge_gas(Gas::new(U512::from(GAS_LIMIT)))?;
+        assert_eq!(
+            runtime_context.gas_counter(),
+            Gas::new(U512::from(GAS_LIMIT))
+        );
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_not_charge_gas_over_limit() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.charge_gas(Gas::new(U512::from(1)))?;
+        match runtime_context.charge_gas(Gas::new(U512::from(GAS_LIMIT))) {
+            Err(Error::GasLimit) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+        // The gas counter is left untouched
+        assert_eq!(runtime_context.gas_counter(), Gas::new(U512::from(1)));
+
+        match runtime_context.charge_gas(Gas::new(U512::MAX)) {
+            Err(Error::GasLimit) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_create_uref_with_access_rights() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let value = StoredValue::CLValue(CLValue::from_t(42_i32).unwrap());
+        let uref =
+            runtime_context.new_uref_with_access_rights(value.clone(), AccessRights::READ)?;
+        assert_eq!(uref.access_rights(), AccessRights::READ);
+
+        let key = Key::URef(uref);
+        assert_eq!(runtime_context.read_gs(&key)?, Some(value));
+        assert_invalid_access(
+            runtime_context.validate_writeable(&key),
+            AccessRights::WRITE,
+        );
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_list_named_keys_in_pages() {
+    let deploy_hash = [1u8; 32];
+    let (base_key, account) = mock_account(AccountHash::new([0u8; 32]));
+    let mut named_keys: NamedKeys = ["a", "b", "c", "d", "e"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.to_string(), Key::Hash([i as u8; 32])))
+        .collect();
+    let runtime_context = mock_runtime_context(
+        &account,
+        base_key,
+        &mut named_keys,
+        HashMap::new(),
+        AddressGenerator::new(&deploy_hash, Phase::Session),
+        AddressGenerator::new(&deploy_hash, Phase::Session),
+    );
+
+    let names = |page: Vec<(&str, &Key)>| -> Vec<String> {
+        page.into_iter().map(|(name, _)| name.to_string()).collect()
+    };
+
+    let first_page = runtime_context.named_keys_page(None, 2);
+    assert_eq!(first_page[0], ("a", &Key::Hash([0; 32])));
+    assert_eq!(names(first_page), vec!["a", "b"]);
+    assert_eq!(
+        names(runtime_context.named_keys_page(Some("b"), 2)),
+        vec!["c", "d"]
+    );
+    assert_eq!(
+        names(runtime_context.named_keys_page(Some("d"), 2)),
+        vec!["e"]
+    );
+    assert!(runtime_context.named_keys_page(Some("e"), 2).is_empty());
+}
+
+#[test]
+fn should_validate_contract_package_urefs() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE)
+        .into_uref()
+        .expect("should be uref");
+    let group_uref = URef::new([42; 32], AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key, Key::URef(access_key)]);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let contract_package = ContractPackage::new(
+            access_key,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        );
+        runtime_context.write_gs(uref_key, StoredValue::ContractPackage(contract_package))?;
+
+        let mut contract_package = ContractPackage::new(
+            access_key,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        );
+        contract_package.add_group(Group::new("Group"), iter::once(group_uref).collect());
+        runtime_context.write_gs(uref_key, StoredValue::ContractPackage(contract_package))
+    };
+    let query_result = test(access_rights, query);
+    assert_forged_reference(query_result);
+}
+
+#[test]
+fn remove_keys_works() {
+    let deploy_hash = [1u8; 32];
+    let (base_key, mut account) = mock_account(AccountHash::new([0u8; 32]));
+    let mut named_keys: NamedKeys = ["Foo", "Bar", "Baz"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.to_string(), Key::Hash([i as u8; 32])))
+        .collect();
+    account.named_keys_mut().extend(named_keys.clone());
+    let mut runtime_context = mock_runtime_context(
+        &account,
+        base_key,
+        &mut named_keys,
+        HashMap::new(),
+        AddressGenerator::new(&deploy_hash, Phase::Session),
+        AddressGenerator::new(&deploy_hash, Phase::Session),
+    );
+
+    let tracking_copy = runtime_context.state();
+    assert_eq!(tracking_copy.borrow().write_count(), 0);
+
+    runtime_context
+        .remove_keys(&["Foo", "Bar", "Absent"])
+        .expect("should remove keys");
+
+    // The account is written back once rather than once per removed key
+    assert_eq!(tracking_copy.borrow().write_count(), 1);
+
+    assert!(!runtime_context.named_keys_contains_key("Foo"));
+    assert!(!runtime_context.named_keys_contains_key("Bar"));
+    assert!(runtime_context.named_keys_contains_key("Baz"));
+
+    let effects = runtime_context.effect();
+    let transform = effects.transforms.get(&base_key).unwrap();
+    let account = match transform {
+        Transform::Write(StoredValue::Account(account)) => account,
+        _ => panic!("Invalid transform operation found"),
+    };
+    assert_eq!(account.named_keys().len(), 1);
+    assert!(account.named_keys().contains_key("Baz"));
+}
+
+#[test]
+fn should_cache_reads_until_written() {
+    let account_hash = AccountHash::new([0u8; 32]);
+    let (base_key, account) = mock_account(account_hash);
+    let mut uref_address_generator = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut uref_address_generator, AccessRights::READ_WRITE);
+    let tracking_copy = Rc::new(RefCell::new(mock_tracking_copy(base_key, account.clone())));
+    let mut named_keys = NamedKeys::new();
+    let mut runtime_context = RuntimeContext::new(
+        Rc::clone(&tracking_copy),
+        EntryPointType::Session,
+        &mut named_keys,
+        extract_access_rights_from_keys(vec![uref_key]),
+        RuntimeArgs::new(),
+        BTreeSet::from_iter(vec![account_hash]),
+        &account,
+        base_key,
+        BlockTime::new(0),
+        DEPLOY_HASH,
+        Gas::default(),
+        Gas::default(),
+        Rc::new(RefCell::new(AddressGenerator::new(&DEPLOY_HASH, PHASE))),
+        Rc::new(RefCell::new(uref_address_generator)),
+        ProtocolVersion::V1_0_0,
+        CorrelationId::new(),
+        PHASE,
+        Default::default(),
+    );
+
+    let first_value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let second_value = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+    let read_count = || tracking_copy.borrow().read_count();
+
+    runtime_context
+        .write_gs(uref_key, first_value.clone())
+        .expect("should write");
+    assert_eq!(
+        runtime_context.read_gs(&uref_key).unwrap(),
+        Some(first_value.clone())
+    );
+    assert_eq!(read_count(), 1);
+
+    // The second read is served from the cache without hitting the tracking copy
+    assert_eq!(
+        runtime_context.read_gs(&uref_key).unwrap(),
+        Some(first_value)
+    );
+    assert_eq!(read_count(), 1);
+
+    // Writing through the context invalidates the cached value
+    runtime_context
+        .write_gs(uref_key, second_value.clone())
+        .expect("should write");
+    assert_eq!(
+        runtime_context.read_gs(&uref_key).unwrap(),
+        Some(second_value)
+    );
+    assert_eq!(read_count(), 2);
+}
+
+#[test]
+fn should_check_key_exists() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let missing_uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key, missing_uref_key]);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let base_key = runtime_context.base_key();
+        assert!(runtime_context.key_exists(&base_key)?);
+        // The value read is cached for a following `read_gs`
+        assert!(runtime_context
+            .read_cache
+            .borrow()
+            .contains_key(&base_key.normalize()));
+
+        runtime_context.write_gs(
+            uref_key,
+            StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()),
+        )?;
+        assert!(runtime_context.key_exists(&uref_key)?);
+        assert!(!runtime_context.key_exists(&missing_uref_key)?);
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+#[test]
+fn should_not_check_key_exists_for_forged_uref() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let query_result = test(HashMap::new(), |mut rc| rc.key_exists(&uref_key));
+    assert_forged_reference(query_result);
+}
+
+// Mimics the mint's layout: purse address -> balance URef -> balance.
+fn mock_purse(
+    runtime_context: &RuntimeContext<InMemoryGlobalStateView>,
+    purse: URef,
+    balance_key: Key,
+    balance: U512,
+) {
+    let tracking_copy = runtime_context.state();
+    let mut tracking_copy = tracking_copy.borrow_mut();
+    tracking_copy.write(
+        Key::Hash(purse.addr()),
+        StoredValue::CLValue(CLValue::from_t(balance_key).unwrap()),
+    );
+    tracking_copy.write(
+        balance_key,
+        StoredValue::CLValue(CLValue::from_t(balance).unwrap()),
+    );
+}
+
+#[test]
+fn should_get_purse_balance() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let purse = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let unknown_purse = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let balance_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![purse, unknown_purse]);
+    let balance = U512::from(1_000);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let purse = purse.into_uref().unwrap();
+        mock_purse(&runtime_context, purse, balance_key, balance);
+
+        assert_eq!(runtime_context.get_purse_balance(purse)?, Some(balance));
+        assert_eq!(
+            runtime_context.get_purse_balance(unknown_purse.into_uref().unwrap())?,
+            None
+        );
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+#[test]
+fn should_report_gas_remaining() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        assert_eq!(
+            runtime_context.gas_remaining(),
+            Gas::new(U512::from(GAS_LIMIT))
+        );
+
+        runtime_context.charge_gas(Gas::new(U512::from(100)))?;
+        assert_eq!(
+            runtime_context.gas_remaining(),
+            Gas::new(U512::from(GAS_LIMIT - 100))
+        );
+        assert!(runtime_context.has_gas_for(Gas::new(U512::from(GAS_LIMIT - 100))));
+        assert!(!runtime_context.has_gas_for(Gas::new(U512::from(GAS_LIMIT - 99))));
+
+        // Saturates at zero if the gas counter was pushed past the limit
+        runtime_context.set_gas_counter(Gas::new(U512::from(GAS_LIMIT + 1)));
+        assert_eq!(runtime_context.gas_remaining(), Gas::default());
+        assert!(!runtime_context.has_gas_for(Gas::new(U512::from(1))));
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_write_and_read_typed_local_storage() {
+    let key_bytes = [3u8; KEY_HASH_LENGTH];
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        assert_eq!(runtime_context.read_ls_typed::<u64>(&key_bytes)?, None);
+        runtime_context.write_ls_typed(&key_bytes, 42_u64)?;
+        assert_eq!(runtime_context.read_ls_typed::<u64>(&key_bytes)?, Some(42));
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_not_read_typed_local_storage_with_wrong_type() {
+    let key_bytes = [3u8; KEY_HASH_LENGTH];
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.write_ls_typed(&key_bytes, 42_u64)?;
+        runtime_context.read_ls_typed::<String>(&key_bytes)
+    };
+    match test(HashMap::new(), query) {
+        Err(Error::TypeMismatch(_)) => {}
+        other => panic!("Invalid result: {:?}", other),
+    }
+}
+
+#[test]
+fn should_not_write_typed_local_storage_with_invalid_key_length() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.write_ls_typed(&[3u8; KEY_HASH_LENGTH - 1], 42_u64)
+    };
+    match test(HashMap::new(), query) {
+        Err(Error::InvalidKeyLength { actual, expected }) => {
+            assert_eq!(actual, KEY_HASH_LENGTH - 1);
+            assert_eq!(expected, KEY_HASH_LENGTH);
+        }
+        other => panic!("Invalid result: {:?}", other),
+    }
+}
+
+#[test]
+fn should_transfer_between_purses() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let source = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let target = create_uref(&mut rng, AccessRights::READ_ADD);
+    let source_balance_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let target_balance_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![source, target]);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let source = source.into_uref().unwrap();
+        let target = target.into_uref().unwrap();
+        mock_purse(
+            &runtime_context,
+            source,
+            source_balance_key,
+            U512::from(100),
+        );
+        mock_purse(&runtime_context, target, target_balance_key, U512::from(5));
+
+        runtime_context.transfer_from_purse_to_purse(source, target, U512::from(40))?;
+        assert_eq!(
+            runtime_context.get_purse_balance(source)?,
+            Some(U512::from(60))
+        );
+        assert_eq!(
+            runtime_context.get_purse_balance(target)?,
+            Some(U512::from(45))
+        );
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+#[test]
+fn should_not_transfer_with_insufficient_funds() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let source = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let target = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let source_balance_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let target_balance_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![source, target]);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let source = source.into_uref().unwrap();
+        let target = target.into_uref().unwrap();
+        mock_purse(
+            &runtime_context,
+            source,
+            source_balance_key,
+            U512::from(100),
+        );
+        mock_purse(&runtime_context, target, target_balance_key, U512::from(5));
+
+        match runtime_context.transfer_from_purse_to_purse(source, target, U512::from(101)) {
+            Err(Error::InsufficientFunds) => {}
+            other => panic!("Invalid result: {:?}", other),
+        }
+        // Neither balance is touched
+        assert_eq!(
+            runtime_context.get_purse_balance(source)?,
+            Some(U512::from(100))
+        );
+        assert_eq!(
+            runtime_context.get_purse_balance(target)?,
+            Some(U512::from(5))
+        );
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+#[test]
+fn should_not_transfer_from_purse_without_write_access() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let source = create_uref(&mut rng, AccessRights::READ_ADD);
+    let target = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![source, target]);
+    let query_result = test(access_rights, |mut rc| {
+        rc.transfer_from_purse_to_purse(
+            source.into_uref().unwrap(),
+            target.into_uref().unwrap(),
+            U512::from(1),
+        )
+    });
+    assert_invalid_access(query_result, AccessRights::WRITE);
+}
+
+#[test]
+fn should_report_authorization_weight_meeting_key_management_threshold() {
+    let query = |runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        // The only authorization key is the account's own key with weight 1, which meets the
+        // default key management threshold of 1
+        assert_eq!(runtime_context.authorization_weight(), Weight::new(1));
+        assert!(runtime_context.can_manage_keys());
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_report_authorization_weight_below_key_management_threshold() {
+    let account_hash = AccountHash::new([0u8; 32]);
+    let mut associated_keys = AssociatedKeys::new(account_hash, Weight::new(1));
+    associated_keys
+        .add_key(AccountHash::new([1u8; 32]), Weight::new(2))
+        .expect("should add key");
+    let account = Account::new(
+        account_hash,
+        NamedKeys::new(),
+        URef::new([0; 32], AccessRights::READ_ADD_WRITE),
+        associated_keys,
+        ActionThresholds::new(Weight::new(1), Weight::new(3)).expect("should create thresholds"),
+    );
+    let base_key = Key::Account(account_hash);
+    let mut named_keys = NamedKeys::new();
+    let runtime_context = mock_runtime_context(
+        &account,
+        base_key,
+        &mut named_keys,
+        HashMap::new(),
+        AddressGenerator::new(&DEPLOY_HASH, PHASE),
+        AddressGenerator::new(&DEPLOY_HASH, PHASE),
+    );
+
+    // The second associated key didn't sign the deploy, so only the account's own weight counts
+    assert_eq!(runtime_context.authorization_weight(), Weight::new(1));
+    assert!(!runtime_context.can_manage_keys());
+}
+
+fn assert_read_only<T: std::fmt::Debug>(result: Result<T, Error>) {
+    match result {
+        Err(Error::ReadOnlyContext) => {}
+        other => panic!("Expected ReadOnlyContext error but got: {:?}", other),
+    }
+}
+
+#[test]
+fn should_block_mutations_in_read_only_mode() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let other_uref_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key, other_uref_key]);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+        runtime_context.put_key("existing".to_string(), uref_key)?;
+        runtime_context.set_read_only(true);
+        assert!(runtime_context.is_read_only());
+
+        let base_key = runtime_context.base_key();
+        let account = runtime_context.account().clone();
+        let uref = uref_key.into_uref().unwrap();
+        let other_uref = other_uref_key.into_uref().unwrap();
+        let account_hash = AccountHash::new([42; 32]);
+
+        assert_read_only(runtime_context.write_gs(uref_key, value.clone()));
+        assert_read_only(runtime_context.add_gs(uref_key, value.clone()));
+        assert_read_only(runtime_context.write_account(base_key, account));
+        assert_read_only(
+            runtime_context.write_ls(&[3u8; KEY_HASH_LENGTH], CLValue::from_t(1_i32).unwrap()),
+        );
+        assert_read_only(runtime_context.put_key("new".to_string(), uref_key));
+        assert_read_only(runtime_context.remove_key("existing"));
+        assert_read_only(runtime_context.new_uref(value.clone()));
+        assert_read_only(runtime_context.store_function_at_hash(value));
+        assert_read_only(runtime_context.add_associated_key(account_hash, Weight::new(1)));
+        assert_read_only(runtime_context.remove_associated_key(account_hash));
+        assert_read_only(runtime_context.update_associated_key(account_hash, Weight::new(1)));
+        assert_read_only(
+            runtime_context.set_action_threshold(ActionType::Deployment, Weight::new(1)),
+        );
+        assert_read_only(runtime_context.update_account(|_| Ok(())));
+        assert_read_only(runtime_context.transfer_from_purse_to_purse(
+            uref,
+            other_uref,
+            U512::from(1),
+        ));
+
+        // Nothing was changed, and reads still work
+        assert!(runtime_context.named_keys_contains_key("existing"));
+        assert!(!runtime_context.named_keys_contains_key("new"));
+        assert!(runtime_context.read_gs(&base_key)?.is_some());
+        assert_eq!(runtime_context.read_ls(&[3u8; KEY_HASH_LENGTH])?, None);
+
+        runtime_context.set_read_only(false);
+        runtime_context.remove_key("existing")?;
+        assert!(!runtime_context.named_keys_contains_key("existing"));
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+fn mock_runtime(
+    runtime_context: RuntimeContext<InMemoryGlobalStateView>,
+) -> Runtime<InMemoryGlobalStateView> {
+    let memory = MemoryInstance::alloc(Pages(1), None).unwrap();
+    Runtime::new(
+        EngineConfig::default(),
+        SystemContractCache::default(),
+        memory,
+        Module::default(),
+        runtime_context,
+    )
+}
+
+#[test]
+fn should_block_mutations_in_nested_call_in_read_only_mode() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let source = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let target = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let source_balance_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let target_balance_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![source, target]);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let source = source.into_uref().unwrap();
+        let target = target.into_uref().unwrap();
+        mock_purse(
+            &runtime_context,
+            source,
+            source_balance_key,
+            U512::from(100),
+        );
+        mock_purse(&runtime_context, target, target_balance_key, U512::from(5));
+        runtime_context.set_read_only(true);
+        let mut runtime = mock_runtime(runtime_context);
+
+        let mut mint_named_keys = NamedKeys::new();
+        mint_named_keys.insert("source".to_string(), source_balance_key);
+        mint_named_keys.insert("target".to_string(), target_balance_key);
+        let mut args = RuntimeArgs::new();
+        args.insert("source", source);
+        args.insert("target", target);
+        args.insert("amount", U512::from(40));
+        assert_read_only(runtime.call_host_mint(
+            ProtocolVersion::V1_0_0,
+            "transfer",
+            &mut mint_named_keys,
+            &args,
+            &[],
+        ));
+
+        // Entry points which only read are still allowed
+        let mut args = RuntimeArgs::new();
+        args.insert("purse", source);
+        let balance = runtime.call_host_mint(
+            ProtocolVersion::V1_0_0,
+            "balance",
+            &mut mint_named_keys,
+            &args,
+            &[],
+        )?;
+        assert_eq!(balance, CLValue::from_t(Some(U512::from(100))).unwrap());
+
+        let runtime_context = runtime.context();
+        let tracking_copy = runtime_context.state();
+        let mut tracking_copy = tracking_copy.borrow_mut();
+        let correlation_id = runtime_context.correlation_id();
+        for (balance_key, balance) in &[(source_balance_key, 100), (target_balance_key, 5)] {
+            let value = tracking_copy.read(correlation_id, balance_key).unwrap();
+            assert_eq!(
+                value,
+                Some(StoredValue::CLValue(
+                    CLValue::from_t(U512::from(*balance)).unwrap()
+                ))
+            );
+        }
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+#[test]
+fn should_not_create_purse_in_read_only_mode() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.set_read_only(true);
+        let mut runtime = mock_runtime(runtime_context);
+
+        let mut args = RuntimeArgs::new();
+        args.insert("amount", U512::zero());
+        for entry_point_name in &["create", "mint"] {
+            assert_read_only(runtime.call_host_mint(
+                ProtocolVersion::V1_0_0,
+                entry_point_name,
+                &mut NamedKeys::new(),
+                &args,
+                &[],
+            ));
+        }
+        assert!(runtime.context().effect().transforms.is_empty());
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_not_call_writing_proof_of_stake_entry_points_in_read_only_mode() {
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        runtime_context.set_read_only(true);
+        let mut runtime = mock_runtime(runtime_context);
+
+        for entry_point_name in &["bond", "unbond", "set_refund_purse", "finalize_payment"] {
+            assert_read_only(runtime.call_host_proof_of_stake(
+                ProtocolVersion::V1_0_0,
+                entry_point_name,
+                &mut NamedKeys::new(),
+                &RuntimeArgs::new(),
+                &[],
+            ));
+        }
+        Ok(())
+    };
+    test(HashMap::new(), query).expect("should be ok");
+}
+
+#[test]
+fn should_read_many_keys_in_order() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let first_uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let missing_uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let second_uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let access_rights =
+        extract_access_rights_from_keys(vec![first_uref_key, missing_uref_key, second_uref_key]);
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        let first_value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+        let second_value = StoredValue::CLValue(CLValue::from_t(2_i32).unwrap());
+        runtime_context.write_gs(first_uref_key, first_value.clone())?;
+        runtime_context.write_gs(second_uref_key, second_value.clone())?;
+
+        let values =
+            runtime_context.read_gs_many(&[second_uref_key, missing_uref_key, first_uref_key])?;
+        assert_eq!(values, vec![Some(second_value), None, Some(first_value)]);
+        Ok(())
+    };
+    test(access_rights, query).expect("should be ok");
+}
+
+#[test]
+fn should_not_read_many_keys_with_forged_uref() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let first_uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let forged_uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let second_uref_key = create_uref(&mut rng, AccessRights::READ_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![first_uref_key, second_uref_key]);
+    let query_result = test(access_rights, |mut rc| {
+        rc.read_gs_many(&[first_uref_key, forged_uref_key, second_uref_key])
+    });
+    assert_forged_reference(query_result);
+}
@@ -154,6 +154,13 @@ pub struct TrackingCopy<R> {
     cache: TrackingCopyCache<HeapSize>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    // Number of calls to `write`, which can't be recovered from `fns` as writes to the same key
+    // collapse into a single transform
+    #[cfg(test)]
+    write_count: usize,
+    // Number of calls to `read`, which can't be recovered from `ops` for the same reason
+    #[cfg(test)]
+    read_count: usize,
 }
 
 #[derive(Debug)]
@@ -187,6 +194,10 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
              * limit? */
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            #[cfg(test)]
+            write_count: 0,
+            #[cfg(test)]
+            read_count: 0,
         }
     }
 
@@ -231,6 +242,10 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
+        #[cfg(test)]
+        {
+            self.read_count += 1;
+        }
         let normalized_key = key.normalize();
         if let Some(value) = self.get(correlation_id, &normalized_key)? {
             self.ops.insert_add(normalized_key, Op::Read);
@@ -246,6 +261,20 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         self.cache.insert_write(normalized_key, value.clone());
         self.ops.insert_add(normalized_key, Op::Write);
         self.fns.insert_add(normalized_key, Transform::Write(value));
+        #[cfg(test)]
+        {
+            self.write_count += 1;
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn write_count(&self) -> usize {
+        self.write_count
+    }
+
+    #[cfg(test)]
+    pub(crate) fn read_count(&self) -> usize {
+        self.read_count
     }
 
     /// Ok(None) represents missing key to which we want to "add" some value.
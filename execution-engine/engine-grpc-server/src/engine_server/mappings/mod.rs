@@ -23,7 +23,7 @@ pub(crate) fn vec_to_array(input: Vec<u8>, input_name: &str) -> Result<[u8; 32],
         .map_err(|_| format!("{} must be 32 bytes.", input_name).into())
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum MappingError {
     InvalidStateHashLength { expected: usize, actual: usize },
     InvalidAccountHashLength { expected: usize, actual: usize },
@@ -33,6 +33,13 @@ pub enum MappingError {
     InvalidStateHash(String),
     MissingPayload,
     TryFromSlice,
+    InvalidURef,
+    InvalidContractHash,
+    InvalidContractPackageHash,
+    InvalidContractWasmHash,
+    InvalidContractVersion,
+    MissingEntryPointAccess,
+    MissingEntryPointType,
 }
 
 impl MappingError {
@@ -97,6 +104,21 @@ impl Display for MappingError {
                 "Invalid hash length: expected {}, actual {}",
                 expected, actual
             ),
+            MappingError::InvalidURef => write!(f, "Unable to parse Protobuf URef"),
+            MappingError::InvalidContractHash => write!(f, "Unable to parse contract hash"),
+            MappingError::InvalidContractPackageHash => {
+                write!(f, "Unable to parse contract package hash")
+            }
+            MappingError::InvalidContractWasmHash => {
+                write!(f, "Unable to parse contract wasm hash")
+            }
+            MappingError::InvalidContractVersion => write!(f, "Invalid value for contract version"),
+            MappingError::MissingEntryPointAccess => {
+                write!(f, "Unable to parse Protobuf entry point access")
+            }
+            MappingError::MissingEntryPointType => {
+                write!(f, "Unable to parse Protobuf entry point type")
+            }
         }
     }
 }
@@ -142,3 +142,63 @@ impl TryFrom<state::CLType> for CLType {
         Ok(cl_type)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(cl_type in gens::cl_type_arb()) {
+            test_utils::protobuf_round_trip::<CLType, state::CLType>(cl_type);
+        }
+    }
+
+    #[test]
+    fn should_round_trip_every_variant() {
+        let nested = || {
+            Box::new(CLType::Map {
+                key: Box::new(CLType::String),
+                value: Box::new(CLType::Option(Box::new(CLType::URef))),
+            })
+        };
+        let cl_types = vec![
+            CLType::Bool,
+            CLType::I32,
+            CLType::I64,
+            CLType::U8,
+            CLType::U32,
+            CLType::U64,
+            CLType::U128,
+            CLType::U256,
+            CLType::U512,
+            CLType::Unit,
+            CLType::String,
+            CLType::Key,
+            CLType::URef,
+            CLType::Option(nested()),
+            CLType::List(nested()),
+            CLType::FixedList(nested(), 32),
+            CLType::Result {
+                ok: nested(),
+                err: Box::new(CLType::U32),
+            },
+            CLType::Map {
+                key: Box::new(CLType::Key),
+                value: nested(),
+            },
+            CLType::Tuple1([nested()]),
+            CLType::Tuple2([nested(), Box::new(CLType::Unit)]),
+            CLType::Tuple3([nested(), Box::new(CLType::Unit), Box::new(CLType::Any)]),
+            CLType::Any,
+        ];
+        for cl_type in cl_types {
+            test_utils::protobuf_round_trip::<CLType, state::CLType>(cl_type);
+        }
+    }
+}
@@ -4,7 +4,7 @@ use types::{
 };
 
 use super::NamedKeyMap;
-use crate::engine_server::{mappings::ParsingError, state};
+use crate::engine_server::{mappings::MappingError, state};
 use std::convert::{TryFrom, TryInto};
 
 impl From<Contract> for state::Contract {
@@ -28,7 +28,7 @@ impl From<Contract> for state::Contract {
 }
 
 impl TryFrom<state::Contract> for Contract {
-    type Error = ParsingError;
+    type Error = MappingError;
     fn try_from(mut value: state::Contract) -> Result<Self, Self::Error> {
         let named_keys = {
             let mut named_keys = NamedKeys::new();
@@ -42,13 +42,13 @@ impl TryFrom<state::Contract> for Contract {
             .contract_package_hash
             .as_slice()
             .try_into()
-            .map_err(|_| ParsingError::from("Unable to parse contract package hash"))?;
+            .map_err(|_| MappingError::InvalidContractPackageHash)?;
         let contract_wasm_hash: ContractWasmHash =
             value
                 .contract_wasm_hash
                 .as_slice()
                 .try_into()
-                .map_err(|_| ParsingError::from("Unable to parse contract package hash"))?;
+                .map_err(|_| MappingError::InvalidContractWasmHash)?;
 
         let mut entry_points = EntryPoints::new();
         for entry_point in value.take_entry_points().into_iter() {
@@ -65,13 +65,22 @@ impl TryFrom<state::Contract> for Contract {
     }
 }
 
+impl state::Contract {
+    /// Finds the Protobuf entry point called `name` without converting the whole contract.
+    pub fn find_entry_point(&self, name: &str) -> Option<&state::Contract_EntryPoint> {
+        self.get_entry_points()
+            .iter()
+            .find(|entry_point| entry_point.get_name() == name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::proptest;
 
     use super::*;
     use crate::engine_server::mappings::test_utils;
-    use types::gens;
+    use types::{gens, CLType, EntryPoint, EntryPointAccess, EntryPointType, ProtocolVersion};
 
     proptest! {
 
@@ -80,4 +89,33 @@ mod tests {
             test_utils::protobuf_round_trip::<Contract, state::Contract>(contract);
         }
     }
+
+    #[test]
+    fn should_find_entry_point() {
+        let mut entry_points = EntryPoints::new();
+        entry_points.add_entry_point(EntryPoint::new(
+            "transfer",
+            vec![],
+            CLType::Unit,
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+        let contract = Contract::new(
+            [1; 32],
+            [2; 32],
+            NamedKeys::new(),
+            entry_points.clone(),
+            ProtocolVersion::V1_0_0,
+        );
+        let pb_contract = state::Contract::from(contract);
+
+        let pb_entry_point = pb_contract
+            .find_entry_point("transfer")
+            .expect("should find entry point");
+        assert_eq!(
+            &EntryPoint::try_from(pb_entry_point.clone()).unwrap(),
+            entry_points.get("transfer").unwrap()
+        );
+        assert!(pb_contract.find_entry_point("missing").is_none());
+    }
 }
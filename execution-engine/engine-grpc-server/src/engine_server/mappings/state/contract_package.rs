@@ -8,7 +8,7 @@ use types::{
     Parameter,
 };
 
-use crate::engine_server::{mappings::ParsingError, state};
+use crate::engine_server::{mappings::MappingError, state};
 
 impl From<ContractPackage> for state::ContractPackage {
     fn from(value: ContractPackage) -> state::ContractPackage {
@@ -47,7 +47,7 @@ impl From<ContractPackage> for state::ContractPackage {
 }
 
 impl TryFrom<state::ContractPackage> for ContractPackage {
-    type Error = ParsingError;
+    type Error = MappingError;
     fn try_from(mut value: state::ContractPackage) -> Result<ContractPackage, Self::Error> {
         let access_uref = value.take_access_key().try_into()?;
         let mut contract_package = ContractPackage::new(
@@ -58,7 +58,11 @@ impl TryFrom<state::ContractPackage> for ContractPackage {
         );
         for mut active_version in value.take_active_versions().into_iter() {
             let version = active_version.take_version().try_into()?;
-            let header = active_version.take_contract_hash().as_slice().try_into()?;
+            let header = active_version
+                .take_contract_hash()
+                .as_slice()
+                .try_into()
+                .map_err(|_| MappingError::InvalidContractHash)?;
             contract_package.versions_mut().insert(version, header);
         }
         for disabled_version in value.take_disabled_versions().into_iter() {
@@ -126,7 +130,7 @@ impl From<EntryPoint> for state::Contract_EntryPoint {
 }
 
 impl TryFrom<state::Contract_EntryPoint> for EntryPoint {
-    type Error = ParsingError;
+    type Error = MappingError;
     fn try_from(mut value: state::Contract_EntryPoint) -> Result<EntryPoint, Self::Error> {
         let name = value.take_name();
         let mut args = Vec::new();
@@ -149,7 +153,7 @@ impl TryFrom<state::Contract_EntryPoint> for EntryPoint {
                 }
                 EntryPointAccess::Groups(vec)
             }
-            None => return Err("Unable to parse Protobuf entry point access".into()),
+            None => return Err(MappingError::MissingEntryPointAccess),
         };
         let entry_point_type = match value.entry_point_type {
             Some(state::Contract_EntryPoint_oneof_entry_point_type::session(_)) => {
@@ -158,7 +162,7 @@ impl TryFrom<state::Contract_EntryPoint> for EntryPoint {
             Some(state::Contract_EntryPoint_oneof_entry_point_type::contract(_)) => {
                 EntryPointType::Contract
             }
-            None => return Err("Unable to parse Protobuf entry point type".into()),
+            None => return Err(MappingError::MissingEntryPointType),
         };
         Ok(EntryPoint::new(
             name,
@@ -180,12 +184,12 @@ impl From<ContractVersionKey> for state::ContractVersionKey {
 }
 
 impl TryFrom<state::ContractVersionKey> for ContractVersionKey {
-    type Error = ParsingError;
+    type Error = MappingError;
     fn try_from(value: state::ContractVersionKey) -> Result<Self, Self::Error> {
         let contract_version = value
             .contract_version
             .try_into()
-            .map_err(|_| ParsingError("Invalid value for contract version".to_string()))?;
+            .map_err(|_| MappingError::InvalidContractVersion)?;
         Ok(ContractVersionKey::new(
             value.protocol_version_major,
             contract_version,
@@ -199,7 +203,7 @@ mod tests {
 
     use super::*;
     use crate::engine_server::mappings::test_utils;
-    use types::gens;
+    use types::{gens, AccessRights, CLType, URef};
 
     proptest! {
 
@@ -208,4 +212,64 @@ mod tests {
             test_utils::protobuf_round_trip::<ContractPackage, state::ContractPackage>(contract);
         }
     }
+
+    #[test]
+    fn should_round_trip_versions_and_groups() {
+        let versions: ContractVersions = vec![
+            (ContractVersionKey::new(2, 1), [1; 32]),
+            (ContractVersionKey::new(1, 2), [2; 32]),
+            (ContractVersionKey::new(1, 1), [3; 32]),
+        ]
+        .into_iter()
+        .collect();
+        let disabled_versions: DisabledVersions =
+            vec![ContractVersionKey::new(1, 1), ContractVersionKey::new(1, 2)]
+                .into_iter()
+                .collect();
+        let groups: Groups = vec![
+            (
+                Group::new("admins"),
+                vec![
+                    URef::new([4; 32], AccessRights::READ),
+                    URef::new([5; 32], AccessRights::READ_ADD_WRITE),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            (Group::new("nobody"), BTreeSet::new()),
+        ]
+        .into_iter()
+        .collect();
+        let contract_package = ContractPackage::new(
+            URef::new([6; 32], AccessRights::READ_ADD_WRITE),
+            versions,
+            disabled_versions,
+            groups,
+        );
+        test_utils::protobuf_round_trip::<ContractPackage, state::ContractPackage>(
+            contract_package,
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_entry_point_without_access() {
+        let mut pb_entry_point = state::Contract_EntryPoint::new();
+        pb_entry_point.set_ret(CLType::Unit.into());
+        pb_entry_point.set_session(state::Contract_EntryPoint_SessionType::new());
+        assert_eq!(
+            EntryPoint::try_from(pb_entry_point),
+            Err(MappingError::MissingEntryPointAccess)
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_entry_point_without_type() {
+        let mut pb_entry_point = state::Contract_EntryPoint::new();
+        pb_entry_point.set_ret(CLType::Unit.into());
+        pb_entry_point.set_public(state::Contract_EntryPoint_Public::new());
+        assert_eq!(
+            EntryPoint::try_from(pb_entry_point),
+            Err(MappingError::MissingEntryPointType)
+        );
+    }
 }
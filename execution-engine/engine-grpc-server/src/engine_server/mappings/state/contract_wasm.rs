@@ -19,9 +19,11 @@ impl From<state::ContractWasm> for ContractWasm {
 mod tests {
     use proptest::proptest;
 
+    use engine_shared::stored_value::StoredValue;
+    use types::gens;
+
     use super::*;
     use crate::engine_server::mappings::test_utils;
-    use types::gens;
 
     proptest! {
         #[test]
@@ -29,4 +31,14 @@ mod tests {
             test_utils::protobuf_round_trip::<ContractWasm, state::ContractWasm>(contract_wasm);
         }
     }
+
+    #[test]
+    fn should_round_trip_wasm_bytes_as_stored_value() {
+        // wasm magic number and version
+        let contract_wasm = ContractWasm::new(vec![0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00]);
+        test_utils::protobuf_round_trip::<ContractWasm, state::ContractWasm>(contract_wasm.clone());
+        test_utils::protobuf_round_trip::<StoredValue, state::StoredValue>(
+            StoredValue::ContractWasm(contract_wasm),
+        );
+    }
 }
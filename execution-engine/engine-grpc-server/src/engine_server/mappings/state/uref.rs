@@ -3,7 +3,7 @@ use std::convert::TryFrom;
 use types::{AccessRights, URef};
 
 use crate::engine_server::{
-    mappings::{self, ParsingError},
+    mappings::{self, MappingError},
     state::{Key_URef, Key_URef_AccessRights},
 };
 
@@ -34,10 +34,11 @@ impl From<URef> for Key_URef {
 }
 
 impl TryFrom<Key_URef> for URef {
-    type Error = ParsingError;
+    type Error = MappingError;
 
     fn try_from(pb_uref: Key_URef) -> Result<Self, Self::Error> {
-        let addr = mappings::vec_to_array(pb_uref.uref, "Protobuf URef addr")?;
+        let addr = mappings::vec_to_array(pb_uref.uref, "Protobuf URef addr")
+            .map_err(|_| MappingError::InvalidURef)?;
 
         let access_rights = match pb_uref.access_rights {
             Key_URef_AccessRights::NONE => AccessRights::NONE,
@@ -90,7 +91,10 @@ mod tests {
 
         let mut pb_uref_invalid_addr = Key_URef::new();
         pb_uref_invalid_addr.set_uref(vec![1; UREF_ADDR_LENGTH - 1]);
-        assert!(URef::try_from(pb_uref_invalid_addr).is_err());
+        assert_eq!(
+            URef::try_from(pb_uref_invalid_addr),
+            Err(MappingError::InvalidURef)
+        );
 
         // Check Protobuf URef with `AccessRights::UNKNOWN` parses to a URef with no access rights.
         let addr: [u8; UREF_ADDR_LENGTH] = rand::random();
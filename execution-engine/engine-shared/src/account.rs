@@ -196,12 +196,17 @@ impl Account {
         total_weight >= *self.action_thresholds().deployment()
     }
 
+    /// Calculates the sum of the weights of the authorization keys which are
+    /// associated with this account.
+    pub fn authorization_weight(&self, authorization_keys: &BTreeSet<AccountHash>) -> Weight {
+        self.associated_keys
+            .calculate_keys_weight(authorization_keys)
+    }
+
     /// Checks whether the sum of the weights of all authorization keys is
     /// greater or equal to key management threshold.
     pub fn can_manage_keys_with(&self, authorization_keys: &BTreeSet<AccountHash>) -> bool {
-        let total_weight = self
-            .associated_keys
-            .calculate_keys_weight(authorization_keys);
+        let total_weight = self.authorization_weight(authorization_keys);
 
         total_weight >= *self.action_thresholds().key_management()
     }